@@ -4,30 +4,118 @@ use tokio::spawn;
 
 // Crate for this project
 // Validation and database connections
-use crate::database::{init, AppState};
+use crate::database::{init, rating_scale_from_env, AppState};
 mod database;
 
+// Single list of every command, it builds both the invoke handler and the
+// capability registry returned by list_capabilities so the two cannot drift
+macro_rules! register_commands {
+    ($($name:ident: { $($flag:ident: $value:expr),* $(,)? }),* $(,)?) => {
+        pub(crate) const COMMAND_CAPABILITIES: &[database::CommandCapability] = &[
+            $(database::CommandCapability { name: stringify!($name), $($flag: $value),* }),*
+        ];
+
+        macro_rules! invoke_handler {
+            () => {
+                tauri::generate_handler![$(database::$name),*]
+            };
+        }
+    };
+}
+
+register_commands! {
+    authenticate: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    logout: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    get_all_watch_items: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    insert_watch_item: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    delete_watch_items: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    audit_data_quality: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    export_as_sql: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_error_history: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    normalize_all_names: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_watch_items_by_types: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    estimate_row_count: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    patch_watch_item: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    can_write: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_prioritized_backlog: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_schema_ddl: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    bulk_insert_watch_items: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_cross_type_duplicates: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    import_from_database: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_rating_buckets: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    restore_from_snapshot: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_recent_rewatchables: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    find_exact: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    export_by_type: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_monthly_additions: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    smart_search: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_extremes: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    delete_by_filter: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    are_duplicates: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    get_watch_stats: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    refresh_stats: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    get_underrated_for_rewatch: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    version_info: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    get_items_by_rating_grouped: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_distinct_name_count: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    database_overview: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    repair_escaped_names: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    update_watch_item: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    search_watch_items: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_items_by_media_type: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_watch_item_count: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    export_watch_list_json: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    export_watch_list_csv: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    import_watch_list_csv: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    insert_watch_items: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    toggle_would_watch_again: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    is_authenticated: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    initialize_database: { requires_auth: true, mutates_data: true, requires_write_privilege: false },
+    ping_database: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+    get_items_by_genre: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    restore_watch_items: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    hard_delete_watch_items: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    pick_random_item: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    upsert_watch_item: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_recent_items: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_items_by_status: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    clear_watch_list: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    update_credentials: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    update_ratings: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    import_watch_list_json: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    get_top_genres: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    get_completion_stats: { requires_auth: true, mutates_data: false, requires_write_privilege: false },
+    mark_watched_now: { requires_auth: true, mutates_data: true, requires_write_privilege: true },
+    list_capabilities: { requires_auth: false, mutates_data: false, requires_write_privilege: false },
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Verbosity is controlled with RUST_LOG, e.g. RUST_LOG=debug
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .init();
+
     tauri::Builder::default()
         .setup(|app| {
             let app_handle = app.handle().clone();
 
+            // Checked here rather than in init, an error from setup stops the app
+            // while a failure inside the spawned task would leave it without state
+            let rating_scale = rating_scale_from_env()?;
+
             // Spawn the async database initialization
             // Calls database::init()
             spawn(async move {
-                init(&app_handle).await;
+                init(&app_handle, rating_scale).await;
             });
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
-            database::authenticate,
-            database::logout,
-            database::get_all_watch_items,
-            database::insert_watch_item,
-            database::delete_watch_items
-        ])
+        .invoke_handler(invoke_handler!())
         .plugin(tauri_plugin_opener::init())
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
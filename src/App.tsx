@@ -1,5 +1,6 @@
-import { Component, createSignal, onMount, Show, For } from 'solid-js';
+import { Component, createSignal, onMount, onCleanup, Show, For } from 'solid-js';
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import { Button } from './components/ui/button';
 import { TextField, TextFieldRoot, TextFieldLabel } from './components/ui/textfield';
 import {Checkbox, CheckboxControl} from './components/ui/checkbox';
@@ -8,20 +9,43 @@ import './App.css';
 import {toast} from "solid-toast";
 
 type MediaType = 'movie' | 'tv';
+type WatchStatus = 'planned' | 'watching' | 'completed' | 'dropped';
 
 interface WatchListItem {
     id?: number;
-    media_type: MediaType;
+    mediaType: MediaType;
     name: string;
     rating: number;
-    would_watch_again: boolean;
+    wouldWatchAgain: boolean;
+    // Defaults to completed on the backend, only completed items must be rated
+    status?: WatchStatus;
+    episodesWatched?: number | null;
+    releaseYear?: number | null;
+    genre?: string | null;
+    notes?: string | null;
+    createdAt?: string | null;
+    updatedAt?: string | null;
 }
 
 interface DatabaseResponse {
     success: boolean;
     message: string;
-    rows_affected: number;
+    rowsAffected: number;
     data?: WatchListItem[];
+    totalCount?: number;
+    limit?: number;
+    offset?: number;
+    hint?: string;
+    errorCode?: string;
+}
+
+interface BatchDeleteResponse {
+    success: boolean;
+    message: string;
+    rowsAffected: number;
+    deleted: number[];
+    notFound: number[];
+    errorCode?: string;
 }
 
 interface AuthResponse {
@@ -43,22 +67,19 @@ interface ValidationError {
 const MAX_NAME_LENGTH = 200;
 const MIN_RATING = 1;
 const MAX_RATING = 10;
+const RATING_STEP = 0.5;
 const MAX_BATCH_DELETE_SIZE = 100;
+// The list is not paged yet, so fetch the largest page the backend allows
+const MAX_PAGE_SIZE = 500;
 
 // Validation patterns
-const NAME_PATTERN = /^[a-zA-Z0-9\s\.,!?\-_()':;&]+$/;
+const NAME_PATTERN = /^[\p{L}\p{M}\p{N}\s.,!?\-_()':;&]+$/u;
 
-// Sanitization function
+// Sanitization function, names are stored as typed and escaped by Solid when rendered
 const sanitizeString = (input: string): string => {
     return input
         .trim()
-        .replace(/</g, '&lt;')
-        .replace(/>/g, '&gt;')
-        .replace(/&/g, '&amp;')
-        .replace(/"/g, '&quot;')
-        .replace(/'/g, '&#x27;')
-        .replace(/\//g, '&#x2F;')
-        .replace(/[^\x20-\x7E\u00C0-\u017F\u0100-\u024F]/g, '')
+        .replace(/\p{Cc}/gu, '')
         .slice(0, MAX_NAME_LENGTH);
 };
 
@@ -86,8 +107,8 @@ const validateRating = (rating: number): ValidationError | null => {
         return { field: 'rating', message: `Rating must be between ${MIN_RATING} and ${MAX_RATING}` };
     }
 
-    if (!Number.isInteger(rating)) {
-        return { field: 'rating', message: 'Rating must be a whole number' };
+    if (!Number.isInteger(rating / RATING_STEP)) {
+        return { field: 'rating', message: 'Rating must be a whole or half point' };
     }
 
     return null;
@@ -127,10 +148,10 @@ const App: Component = () => {
 
     // Form state
     const [formData, setFormData] = createSignal<WatchListItem>({
-        media_type: 'movie',
+        mediaType: 'movie',
         name: '',
         rating: 5,
-        would_watch_again: false
+        wouldWatchAgain: false
     });
 
     // Authentication functions
@@ -194,7 +215,7 @@ const App: Component = () => {
 
         setLoading(true);
         try {
-            const response: DatabaseResponse = await invoke('get_all_watch_items');
+            const response: DatabaseResponse = await invoke('get_all_watch_items', { limit: MAX_PAGE_SIZE });
             if (response.success && response.data) {
                 setWatchList(response.data);
                 createToast(true, response.message);
@@ -238,12 +259,18 @@ const App: Component = () => {
             if (response.success) {
                 createToast(true, response.message);
                 setFormData({
-                    media_type: 'movie',
+                    mediaType: 'movie',
                     name: '',
                     rating: 5,
-                    would_watch_again: false
+                    wouldWatchAgain: false
                 });
-                await loadWatchList();
+                // The response carries the stored row, so append it instead of reloading
+                const inserted = response.data?.[0];
+                if (inserted) {
+                    setWatchList(prev => [...prev, inserted]);
+                } else {
+                    await loadWatchList();
+                }
             } else {
                 createToast(false, response.message);
                 if (response.message.includes('Authentication required')) {
@@ -282,7 +309,7 @@ const App: Component = () => {
 
         setLoading(true);
         try {
-            const response: DatabaseResponse = await invoke('delete_watch_items', { ids: selected });
+            const response: BatchDeleteResponse = await invoke('delete_watch_items', { ids: selected });
             if (response.success) {
                 createToast(true, response.message);
                 setSelectedIds([]);
@@ -351,7 +378,7 @@ const App: Component = () => {
     };
 
     const handleRatingInput = (value: string) => {
-        const num = parseInt(value, 10);
+        const num = parseFloat(value);
         if (isNaN(num)) return;
 
         if (clientValidationEnabled()) {
@@ -390,6 +417,33 @@ const App: Component = () => {
 
     onMount(() => {
         // Don't load watch list on mount - wait for authentication
+
+        // The backend logs out idle sessions, return to the login screen when it does
+        const unlisten = listen('session-expired', () => {
+            setIsAuthenticated(false);
+            setWatchList([]);
+            setSelectedIds([]);
+            createToast(false, 'Session expired after inactivity. Please login again.');
+        });
+        // Writes from any window are broadcast, reload so every window stays current
+        const unlistenChanges = listen('watchlist-changed', () => {
+            if (isAuthenticated()) {
+                loadWatchList();
+            }
+        });
+        // Sent by the backend health check only when the connection state changes
+        const unlistenStatus = listen<'healthy' | 'unhealthy'>('connection-status', (event) => {
+            if (event.payload === 'unhealthy') {
+                createToast(false, 'Lost connection to the database, trying to reconnect...');
+            } else {
+                createToast(true, 'Reconnected to the database');
+            }
+        });
+        onCleanup(() => {
+            unlisten.then(stop => stop());
+            unlistenChanges.then(stop => stop());
+            unlistenStatus.then(stop => stop());
+        });
     });
 
     // Login Screen
@@ -481,12 +535,12 @@ const App: Component = () => {
                                 </div>
                                 <div class="stat-divider" />
                                 <div class="stat-item">
-                                    <span class="stat-value">{watchList().filter(item => item.media_type === 'movie').length}</span>
+                                    <span class="stat-value">{watchList().filter(item => item.mediaType === 'movie').length}</span>
                                     <span class="stat-label">Movies</span>
                                 </div>
                                 <div class="stat-divider" />
                                 <div class="stat-item">
-                                    <span class="stat-value">{watchList().filter(item => item.media_type === 'tv').length}</span>
+                                    <span class="stat-value">{watchList().filter(item => item.mediaType === 'tv').length}</span>
                                     <span class="stat-label">TV Shows</span>
                                 </div>
                                 <div class="stat-divider" />
@@ -591,8 +645,8 @@ const App: Component = () => {
                                                     type="radio"
                                                     name="media_type"
                                                     value="movie"
-                                                    checked={formData().media_type === 'movie'}
-                                                    onChange={() => setFormData(prev => ({ ...prev, media_type: 'movie' }))}
+                                                    checked={formData().mediaType === 'movie'}
+                                                    onChange={() => setFormData(prev => ({ ...prev, mediaType: 'movie' }))}
                                                 />
                                                 <span class="radio-custom"></span>
                                                 <span class="radio-label">🎬 Movie</span>
@@ -602,8 +656,8 @@ const App: Component = () => {
                                                     type="radio"
                                                     name="media_type"
                                                     value="tv"
-                                                    checked={formData().media_type === 'tv'}
-                                                    onChange={() => setFormData(prev => ({ ...prev, media_type: 'tv' }))}
+                                                    checked={formData().mediaType === 'tv'}
+                                                    onChange={() => setFormData(prev => ({ ...prev, mediaType: 'tv' }))}
                                                 />
                                                 <span class="radio-custom"></span>
                                                 <span class="radio-label">📺 TV Show</span>
@@ -643,7 +697,7 @@ const App: Component = () => {
                                                     onInput={(e) => handleRatingInput(e.currentTarget.value)}
                                                     min={clientValidationEnabled() ? MIN_RATING : undefined}
                                                     max={clientValidationEnabled() ? MAX_RATING : undefined}
-                                                    step="1"
+                                                    step={RATING_STEP}
                                                 />
                                             </TextFieldRoot>
                                             <div class="rating-indicator">
@@ -657,8 +711,8 @@ const App: Component = () => {
                                     <div class="checkbox-group">
                                         <label class="checkbox-container">
                                             <Checkbox
-                                                checked={formData().would_watch_again}
-                                                onChange={(checked) => setFormData(prev => ({ ...prev, would_watch_again: checked }))}
+                                                checked={formData().wouldWatchAgain}
+                                                onChange={(checked) => setFormData(prev => ({ ...prev, wouldWatchAgain: checked }))}
                                                 class="flex items-center space-x-2"
                                             >
                                                 <CheckboxControl />
@@ -754,16 +808,19 @@ const App: Component = () => {
                                                     <div class="watch-content">
                                                         <div class="watch-header">
                                                             <div class="watch-title">
-                                                                <span class="media-icon">{getMediaTypeIcon(item.media_type)}</span>
-                                                                <h3 class="watch-name" innerHTML={item.name}></h3>
+                                                                <span class="media-icon">{getMediaTypeIcon(item.mediaType)}</span>
+                                                                <h3 class="watch-name">{item.name}</h3>
                                                             </div>
                                                             <div class="watch-badges">
                                                                 <Badge class="id-badge">#{item.id}</Badge>
-                                                                <Badge class="media-type-badge">{getMediaTypeLabel(item.media_type)}</Badge>
-                                                                <Badge variant="outline" class={`rating-badge ${getRatingColor(item.rating)}`}>
-                                                                    {item.rating}/10
-                                                                </Badge>
-                                                                <Show when={item.would_watch_again}>
+                                                                <Badge class="media-type-badge">{getMediaTypeLabel(item.mediaType)}</Badge>
+                                                                {/* Planned and in-progress items can come back without a rating */}
+                                                                <Show when={item.rating != null}>
+                                                                    <Badge variant="outline" class={`rating-badge ${getRatingColor(item.rating)}`}>
+                                                                        {item.rating}/10
+                                                                    </Badge>
+                                                                </Show>
+                                                                <Show when={item.wouldWatchAgain}>
                                                                     <Badge class="rewatch-badge">Would Rewatch</Badge>
                                                                 </Show>
                                                             </div>